@@ -1,7 +1,8 @@
 use anyhow::{Context, Result, anyhow};
 use chrono::{DateTime, Utc};
 use reqwest::Client;
-use serde::Deserialize;
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::auth::Credentials;
@@ -51,6 +52,15 @@ impl<'de> serde::Deserialize<'de> for PlanType {
     }
 }
 
+impl Serialize for PlanType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl std::fmt::Display for PlanType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -72,7 +82,7 @@ impl std::fmt::Display for PlanType {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct WindowSnapshot {
     #[serde(rename = "used_percent")]
     pub used_percent: i64,
@@ -88,7 +98,7 @@ pub struct WindowSnapshot {
     _extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct RateLimitDetails {
     #[serde(rename = "primary_window")]
     pub primary_window: Option<WindowSnapshot>,
@@ -104,7 +114,7 @@ pub struct RateLimitDetails {
     _extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CreditDetails {
     #[serde(rename = "has_credits")]
     pub has_credits: Option<bool>,
@@ -129,7 +139,7 @@ where
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct UsageResponse {
     #[serde(rename = "plan_type")]
     pub plan_type: Option<PlanType>,
@@ -140,6 +150,11 @@ pub struct UsageResponse {
     _extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+enum FetchOutcome {
+    Success(Box<UsageResponse>),
+    Unauthorized,
+}
+
 pub struct UsageFetcher {
     client: Client,
     base_url: String,
@@ -154,14 +169,38 @@ impl UsageFetcher {
         }
     }
 
-    pub async fn fetch_usage(&self, credentials: &Credentials) -> Result<UsageResponse> {
+    /// Fetches usage, transparently refreshing `credentials` in place on a
+    /// 401/403. The refreshed value is written back into `credentials` (not
+    /// just used for the one retry) so long-lived callers polling in a loop
+    /// keep presenting a live token instead of repeatedly retrying a
+    /// `refresh_token` that the provider already rotated out on first use.
+    pub async fn fetch_usage(&self, credentials: &mut Credentials) -> Result<UsageResponse> {
+        match self.fetch_usage_once(credentials).await? {
+            FetchOutcome::Success(response) => Ok(*response),
+            FetchOutcome::Unauthorized => {
+                let refreshed = credentials.refresh(&self.client).await.context(
+                    "Unauthorized: Token expired or invalid. Run `codex` to re-authenticate.",
+                )?;
+                let outcome = self.fetch_usage_once(&refreshed).await?;
+                *credentials = refreshed;
+                match outcome {
+                    FetchOutcome::Success(response) => Ok(*response),
+                    FetchOutcome::Unauthorized => Err(anyhow!(
+                        "Unauthorized even after refreshing the token. Run `codex` to re-authenticate."
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn fetch_usage_once(&self, credentials: &Credentials) -> Result<FetchOutcome> {
         let url = self.build_usage_url();
         let mut request = self.client.get(&url);
 
         request = request
             .header(
                 "Authorization",
-                format!("Bearer {}", credentials.access_token),
+                format!("Bearer {}", credentials.access_token.expose_secret()),
             )
             .header("User-Agent", "codex-usage")
             .header("Accept", "application/json")
@@ -181,10 +220,9 @@ impl UsageFetcher {
 
         match status.as_u16() {
             200..=299 => serde_json::from_str(&body)
-                .with_context(|| format!("Failed to parse response: {}", body)),
-            401 | 403 => Err(anyhow!(
-                "Unauthorized: Token expired or invalid. Run `codex` to re-authenticate."
-            )),
+                .with_context(|| format!("Failed to parse response: {}", body))
+                .map(|response: UsageResponse| FetchOutcome::Success(Box::new(response))),
+            401 | 403 => Ok(FetchOutcome::Unauthorized),
             code => Err(anyhow!("API error {}: {}", code, body)),
         }
     }
@@ -1,11 +1,23 @@
 use anyhow::{Context, Result};
+use reqwest::Client;
+use secrecy::{ExposeSecret, Secret};
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+/// The Codex CLI's public OAuth client id, used when refreshing tokens the
+/// same way the `codex` binary itself does.
+const OAUTH_CLIENT_ID: &str = "app_EMoamEEZ73f0CkXaXp7hrann";
+const OAUTH_TOKEN_URL: &str = "https://auth.openai.com/oauth/token";
+
+/// Bearer tokens live in `Secret` so `Debug` renders them as `[REDACTED]`
+/// and the backing memory is zeroized on drop — a stray `dbg!` or an error
+/// chain that captures `Credentials` can no longer leak them.
 #[derive(Debug, Clone)]
 pub struct Credentials {
-    pub access_token: String,
+    pub access_token: Secret<String>,
     pub account_id: Option<String>,
+    pub refresh_token: Option<Secret<String>>,
+    pub id_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,6 +32,10 @@ struct AuthJson {
 struct Tokens {
     access_token: String,
     account_id: Option<String>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    id_token: Option<String>,
 }
 
 pub fn load_credentials() -> Result<Credentials> {
@@ -32,8 +48,10 @@ pub fn load_credentials() -> Result<Credentials> {
     if let Some(api_key) = auth.openai_api_key {
         if !api_key.trim().is_empty() {
             return Ok(Credentials {
-                access_token: api_key,
+                access_token: Secret::new(api_key),
                 account_id: None,
+                refresh_token: None,
+                id_token: None,
             });
         }
     }
@@ -43,11 +61,123 @@ pub fn load_credentials() -> Result<Credentials> {
         .context("No tokens found in auth.json. Run `codex` to log in.")?;
 
     Ok(Credentials {
-        access_token: tokens.access_token,
+        access_token: Secret::new(tokens.access_token),
         account_id: tokens.account_id,
+        refresh_token: tokens.refresh_token.map(Secret::new),
+        id_token: tokens.id_token,
     })
 }
 
+#[derive(Debug, Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    id_token: Option<String>,
+}
+
+impl Credentials {
+    /// Exchanges `refresh_token` for a new access token via the ChatGPT
+    /// OAuth token endpoint, persists the result back into `auth.json`, and
+    /// returns the refreshed credentials.
+    pub async fn refresh(&self, client: &Client) -> Result<Credentials> {
+        let refresh_token = self
+            .refresh_token
+            .as_ref()
+            .context("No refresh token available. Run `codex` to re-authenticate.")?;
+
+        let response = client
+            .post(OAUTH_TOKEN_URL)
+            .json(&serde_json::json!({
+                "grant_type": "refresh_token",
+                "client_id": OAUTH_CLIENT_ID,
+                "refresh_token": refresh_token.expose_secret(),
+            }))
+            .send()
+            .await
+            .context("Failed to reach the OAuth token endpoint")?
+            .error_for_status()
+            .context("OAuth token refresh was rejected")?;
+
+        let refreshed: RefreshResponse = response
+            .json()
+            .await
+            .context("Failed to parse OAuth token refresh response")?;
+
+        let credentials = Credentials {
+            access_token: Secret::new(refreshed.access_token),
+            account_id: self.account_id.clone(),
+            refresh_token: refreshed
+                .refresh_token
+                .map(Secret::new)
+                .or_else(|| self.refresh_token.clone()),
+            id_token: refreshed.id_token.or_else(|| self.id_token.clone()),
+        };
+
+        persist_credentials(&credentials)?;
+        Ok(credentials)
+    }
+}
+
+/// Writes the refreshed tokens back into `auth.json`, leaving every other
+/// field (e.g. `OPENAI_API_KEY`, `last_refresh`) untouched.
+fn persist_credentials(credentials: &Credentials) -> Result<()> {
+    let auth_path = get_auth_path()?;
+    let content = std::fs::read_to_string(&auth_path)
+        .with_context(|| format!("Failed to read auth.json from {}", auth_path.display()))?;
+
+    let mut doc: serde_json::Value =
+        serde_json::from_str(&content).context("Failed to parse auth.json")?;
+
+    let tokens = doc
+        .as_object_mut()
+        .context("auth.json is not a JSON object")?
+        .entry("tokens")
+        .or_insert_with(|| serde_json::json!({}));
+
+    let tokens = tokens
+        .as_object_mut()
+        .context("auth.json `tokens` field is not a JSON object")?;
+
+    tokens.insert(
+        "access_token".to_string(),
+        credentials.access_token.expose_secret().clone().into(),
+    );
+    if let Some(refresh_token) = &credentials.refresh_token {
+        tokens.insert(
+            "refresh_token".to_string(),
+            refresh_token.expose_secret().clone().into(),
+        );
+    }
+    if let Some(id_token) = &credentials.id_token {
+        tokens.insert("id_token".to_string(), id_token.clone().into());
+    }
+    if let Some(account_id) = &credentials.account_id {
+        tokens.insert("account_id".to_string(), account_id.clone().into());
+    }
+
+    write_atomic(&auth_path, &serde_json::to_string_pretty(&doc)?)?;
+    Ok(())
+}
+
+/// Writes `contents` to `path` by first writing a sibling temp file and then
+/// renaming it into place. `auth.json` is shared with the `codex` CLI, which
+/// may read it concurrently; a plain `fs::write` truncates before writing
+/// and would hand a reader a half-written file if we crashed mid-write.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let dir = path
+        .parent()
+        .with_context(|| format!("{} has no parent directory", path.display()))?;
+    let tmp_path = dir.join(format!(".{}.tmp.{}", "auth.json", std::process::id()));
+
+    std::fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write temp file {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to move {} into place at {}", tmp_path.display(), path.display()))?;
+    Ok(())
+}
+
 fn get_auth_path() -> Result<PathBuf> {
     let codex_home = std::env::var("CODEX_HOME")
         .ok()
@@ -1,71 +1,162 @@
-use crate::api::{UsageResponse, WindowSnapshot, format_reset_time};
+use crate::api::{format_reset_time, UsageResponse, WindowSnapshot};
+use crate::history::{self, WindowKind};
 use crate::pace::UsagePace;
+use crate::theme::{self, Palette};
 use colored::*;
+use std::io::IsTerminal;
 use strip_ansi_escapes::strip;
 use unicode_width::UnicodeWidthStr;
 
-const WIDTH: usize = 74;
+const MIN_WIDTH: usize = 40;
+const MAX_WIDTH: usize = 100;
+const DEFAULT_WIDTH: usize = 74;
 const TITLE_DECOR: &str = "✦";
+const USAGE_SETTINGS_URL: &str = "https://chatgpt.com/codex/settings/usage";
+
+/// Picks the layout width from the terminal's actual column count, clamped to
+/// a sensible range so output neither gets crushed on a narrow terminal nor
+/// stretches unreadably thin content across a very wide one.
+fn detect_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(cols, _)| cols as usize)
+        .unwrap_or(DEFAULT_WIDTH)
+        .clamp(MIN_WIDTH, MAX_WIDTH)
+}
+
+/// Whether OSC 8 hyperlinks should be emitted: only when stdout is a real
+/// terminal, colors aren't disabled, and the caller didn't pass
+/// `--no-hyperlinks`. Terminals that don't understand OSC 8 just ignore it
+/// and show the wrapped text, but some scripts piping our stdout would
+/// rather see plain text, hence the opt-outs.
+fn hyperlinks_enabled(no_hyperlinks: bool) -> bool {
+    if no_hyperlinks || std::env::var("NO_COLOR").is_ok() {
+        return false;
+    }
+    std::io::stdout().is_terminal()
+}
+
+/// Wraps `text` in an OSC 8 hyperlink to `url` when enabled, otherwise
+/// returns `text` unchanged.
+fn hyperlink(text: &str, url: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        text.to_string()
+    }
+}
 
 fn visible_len(s: &str) -> usize {
-    let stripped = strip(s.as_bytes());
+    let stripped = strip_osc8(s);
+    let stripped = strip(stripped.as_bytes());
     UnicodeWidthStr::width(String::from_utf8_lossy(&stripped).as_ref())
 }
 
-pub fn display_usage(response: &UsageResponse) {
+/// Removes the zero-width OSC 8 escape wrapper (`\x1b]8;;URL\x1b\\` ...
+/// `\x1b]8;;\x1b\\`) around a hyperlink, leaving the visible text behind for
+/// `strip` (which only understands SGR/CSI sequences) to finish cleaning up.
+fn strip_osc8(s: &str) -> String {
+    const START: &str = "\x1b]8;;";
+    const TERMINATOR: &str = "\x1b\\";
+
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start_idx) = rest.find(START) {
+        result.push_str(&rest[..start_idx]);
+        let after_start = &rest[start_idx + START.len()..];
+        let Some(term_idx) = after_start.find(TERMINATOR) else {
+            // No closing terminator: not a well-formed sequence, keep as-is.
+            result.push_str(&rest[start_idx..]);
+            return result;
+        };
+        rest = &after_start[term_idx + TERMINATOR.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+pub fn display_usage(response: &UsageResponse, no_hyperlinks: bool) {
+    let width = detect_width();
+    let palette = if std::env::var("NO_COLOR").is_ok() {
+        colored::control::set_override(false);
+        Palette::Dark
+    } else {
+        theme::detect_palette()
+    };
+    let hyperlinks = hyperlinks_enabled(no_hyperlinks);
+
     let title = format!(
         "{decor} {decor} {decor} CODEX USAGE MONITOR {decor} {decor} {decor}",
         decor = TITLE_DECOR
     );
     let title_colored = title.bold().bright_cyan();
-    print_centered(&title_colored.to_string(), WIDTH);
-    print_rule("=", WIDTH);
+    let title_link = hyperlink(&title_colored.to_string(), USAGE_SETTINGS_URL, hyperlinks);
+    print_centered(&title_link, width);
+    print_rule("=", width);
 
     if let Some(meta_line) = format_meta_line(response) {
-        print_centered(&meta_line, WIDTH);
-        print_rule("-", WIDTH);
+        print_centered(&meta_line, width);
+        print_rule("-", width);
     }
 
     let section_title = "Session-Based Usage Limits".bold();
-    print_line(&section_title.to_string(), WIDTH);
+    print_line(&section_title.to_string(), width);
     let section_subtitle = "Based on rate-limit windows from the API".dimmed();
-    print_line(&section_subtitle.to_string(), WIDTH);
-    print_rule("-", WIDTH);
+    print_line(&section_subtitle.to_string(), width);
+    print_rule("-", width);
 
     if let Some(rate_limit) = &response.rate_limit {
         // 5-hour window (usually primary)
         if let Some(window) = &rate_limit.primary_window {
             let label = format!("5h Window ({})", format_label_minutes(window));
-            display_window_line(window, &label, WIDTH);
+            display_window_line(window, &label, width, palette, hyperlinks);
         }
 
-        print_rule("-", WIDTH);
+        print_rule("-", width);
 
         // Weekly window (usually secondary)
         if let Some(window) = &rate_limit.secondary_window {
             let label = format!("Weekly Window ({})", format_label_minutes(window));
-            display_window_line(window, &label, WIDTH);
+            display_window_line(window, &label, width, palette, hyperlinks);
         }
 
-        print_rule("-", WIDTH);
+        print_rule("-", width);
 
         // Pace for weekly window
         if let Some(window) = &rate_limit.secondary_window {
-            if let Some(pace) = UsagePace::from_window(window, chrono::Utc::now(), 10080) {
+            if let Some((pace, observed)) = pace_for_window(window, WindowKind::Secondary, 10080) {
                 let reset_label_width = reset_label_width(window);
-                display_pace_line(&pace, WIDTH, reset_label_width);
+                display_pace_line(&pace, observed.as_ref(), width, reset_label_width, palette);
             } else if let Some(primary) = &rate_limit.primary_window {
-                if let Some(pace) = UsagePace::from_window(primary, chrono::Utc::now(), 300) {
+                if let Some((pace, observed)) = pace_for_window(primary, WindowKind::Primary, 300) {
                     let reset_label_width = reset_label_width(primary);
-                    display_pace_line(&pace, WIDTH, reset_label_width);
+                    display_pace_line(&pace, observed.as_ref(), width, reset_label_width, palette);
                 }
             }
         }
     } else {
-        print_line("No rate-limit data available.", WIDTH);
+        print_line("No rate-limit data available.", width);
     }
 
-    print_rule("=", WIDTH);
+    print_rule("=", width);
+}
+
+/// Computes pace for `window`, preferring the history-regression-backed
+/// `from_history` (a steadier burn rate and ETA once a few samples have
+/// landed) and falling back to the single-point `from_window` before any
+/// history exists, plus a measured ETA-to-exhaustion derived from recorded
+/// history's observed burn rate when there are at least two in-window
+/// samples to fit a slope through.
+fn pace_for_window(
+    window: &WindowSnapshot,
+    kind: WindowKind,
+    default_window_minutes: i64,
+) -> Option<(UsagePace, Option<UsagePace>)> {
+    let now = chrono::Utc::now();
+    let samples = history::load_samples(kind).unwrap_or_default();
+    let pace = UsagePace::from_history(&samples, now, default_window_minutes)
+        .or_else(|| UsagePace::from_window(window, now, default_window_minutes))?;
+    let observed = UsagePace::observed_eta(&samples, now, default_window_minutes);
+    Some((pace, observed))
 }
 
 fn print_rule(ch: &str, width: usize) {
@@ -87,7 +178,13 @@ fn format_label_minutes(window: &WindowSnapshot) -> String {
     }
 }
 
-fn display_window_line(window: &WindowSnapshot, label: &str, width: usize) {
+fn display_window_line(
+    window: &WindowSnapshot,
+    label: &str,
+    width: usize,
+    palette: Palette,
+    hyperlinks: bool,
+) {
     let used = window.used_percent.clamp(0, 100);
     let remaining = 100i64.saturating_sub(used);
     let reset = format_reset_time(window.reset_at);
@@ -103,17 +200,27 @@ fn display_window_line(window: &WindowSnapshot, label: &str, width: usize) {
     let label_colored = label.bold();
     let percent_str = format!("{:>3}%", remaining);
     let percent_colored = apply_color(&percent_str, status_color);
-    let reset_colored = format!("reset {}", reset).dimmed();
+    let reset_colored = dim_text(&format!("reset {}", reset), palette).to_string();
+    let reset_colored = hyperlink(&reset_colored, USAGE_SETTINGS_URL, hyperlinks);
 
     let label_part = format!("{} {}", indicator, label_colored);
-    let bar_width = width
+    let base_available = width
         .saturating_sub(visible_len(&label_part))
         .saturating_sub(1)
         .saturating_sub(visible_len(&percent_colored.to_string()))
+        .saturating_sub(1);
+
+    // Drop the reset label first when it's the field squeezing the bar below
+    // its floor, rather than letting the bar (the most important part)
+    // shrink or the line overflow.
+    let with_reset = base_available
         .saturating_sub(1)
-        .saturating_sub(visible_len(&reset_colored.to_string()))
-        .saturating_sub(1)
-        .max(10);
+        .saturating_sub(visible_len(&reset_colored.to_string()));
+    let (bar_width, show_reset) = if with_reset >= 10 {
+        (with_reset, true)
+    } else {
+        (base_available.max(10), false)
+    };
 
     let fill_width = ((remaining as f64 / 100.0) * bar_width as f64) as usize;
     let fill = fill_width.min(bar_width);
@@ -123,17 +230,24 @@ fn display_window_line(window: &WindowSnapshot, label: &str, width: usize) {
     let bar = format!(
         "{}{}",
         apply_color(&filled, status_color),
-        apply_color(&empty, "white")
+        apply_color(&empty, neutral_color(palette))
     );
 
-    let line = format!(
-        "{} {} {} {}",
-        label_part, bar, percent_colored, reset_colored
-    );
+    let line = if show_reset {
+        format!("{} {} {} {}", label_part, bar, percent_colored, reset_colored)
+    } else {
+        format!("{} {} {}", label_part, bar, percent_colored)
+    };
     print_line(&line, width);
 }
 
-fn display_pace_line(pace: &UsagePace, width: usize, reset_label_width: usize) {
+fn display_pace_line(
+    pace: &UsagePace,
+    observed: Option<&UsagePace>,
+    width: usize,
+    reset_label_width: usize,
+    palette: Palette,
+) {
     let stage_color = match pace.stage {
         crate::pace::Stage::OnTrack => "green",
         crate::pace::Stage::SlightlyAhead
@@ -146,7 +260,10 @@ fn display_pace_line(pace: &UsagePace, width: usize, reset_label_width: usize) {
     let emoji = pace.stage_emoji();
     let stage_desc = pace.stage_description();
     let delta = pace.format_delta();
-    let eta_val = pace.format_eta();
+    let eta_val = match observed {
+        Some(observed) => format!("{} (obs {})", pace.format_eta(), observed.format_eta()),
+        None => pace.format_eta(),
+    };
 
     // Build the content string (excluding padding)
     let part1_colored = format!(
@@ -155,23 +272,25 @@ fn display_pace_line(pace: &UsagePace, width: usize, reset_label_width: usize) {
         apply_color(stage_desc, stage_color),
         apply_color(&delta, stage_color)
     );
-    let part2_colored = apply_color(&format!("ETA: {}", eta_val), "white");
+    let part2_colored = apply_color(&format!("ETA: {}", eta_val), neutral_color(palette));
 
     let visible_part1 = visible_len(&part1_colored);
     let visible_part2 = visible_len(&part2_colored);
 
+    // Dropping the reset label in display_window_line wasn't enough to make
+    // room: drop the ETA half too rather than let the line overflow.
+    if visible_part1 + 1 + visible_part2 > width {
+        print_line(&part1_colored.to_string(), width);
+        return;
+    }
+
     let reset_start = width.saturating_sub(reset_label_width);
     let max_start = width.saturating_sub(visible_part2);
     let min_start = visible_part1.saturating_add(1);
     let start = reset_start.min(max_start).max(min_start.min(max_start));
     let padding = start.saturating_sub(visible_part1);
 
-    let inner = format!(
-        "{}{}{}",
-        part1_colored,
-        " ".repeat(padding),
-        part2_colored
-    );
+    let inner = format!("{}{}{}", part1_colored, " ".repeat(padding), part2_colored);
     print_line(&inner, width);
 }
 
@@ -190,10 +309,30 @@ fn apply_color(text: &str, color: &str) -> ColoredString {
         "blue" => text.blue(),
         "magenta" => text.magenta(),
         "white" => text.white(),
+        "black" => text.black(),
         _ => text.normal(),
     }
 }
 
+/// Color for the empty-bar glyph and other low-emphasis text: white reads
+/// fine on a dark background but disappears on a light one, so use black
+/// there instead.
+fn neutral_color(palette: Palette) -> &'static str {
+    match palette {
+        Palette::Dark => "white",
+        Palette::Light => "black",
+    }
+}
+
+/// Like `.dimmed()`, but on a light palette dimming washes the text out
+/// entirely, so fall back to plain black.
+fn dim_text(text: &str, palette: Palette) -> ColoredString {
+    match palette {
+        Palette::Dark => text.dimmed(),
+        Palette::Light => text.black(),
+    }
+}
+
 fn print_centered(line: &str, width: usize) {
     let visible = visible_len(line);
     let padding_left = (width.saturating_sub(visible)) / 2;
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Once the history file crosses this size, `record_sample` trims it back
+/// down to `RETAIN_SAMPLES` lines. The file is append-only and re-read in
+/// full on every fetch, so left unbounded it would grow forever and make
+/// every subsequent `load_samples` call progressively slower.
+const MAX_HISTORY_BYTES: u64 = 1_000_000;
+const RETAIN_SAMPLES: usize = 5_000;
+
+/// Which rate-limit window a recorded sample belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowKind {
+    Primary,
+    Secondary,
+}
+
+/// A single observation of usage for a window, appended on every fetch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sample {
+    pub timestamp: i64,
+    pub window: WindowKind,
+    pub used_percent: i64,
+    pub reset_at: i64,
+    pub limit_window_seconds: i64,
+}
+
+/// Appends `sample` to the append-only history file under `CODEX_HOME`.
+pub fn record_sample(sample: &Sample) -> Result<()> {
+    let path = history_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create usage history directory")?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open usage history file {}", path.display()))?;
+
+    let line = serde_json::to_string(sample).context("Failed to serialize usage sample")?;
+    writeln!(file, "{}", line).context("Failed to write usage sample")?;
+    drop(file);
+
+    prune_if_large(&path)
+}
+
+/// Keeps the history file from growing forever: once it crosses
+/// `MAX_HISTORY_BYTES`, rewrites it with only the newest `RETAIN_SAMPLES`
+/// lines. Gated on a cheap file-size stat so the common case (a normal
+/// append) doesn't pay the cost of reading the whole file back.
+fn prune_if_large(path: &Path) -> Result<()> {
+    let len = std::fs::metadata(path)
+        .with_context(|| format!("Failed to stat usage history file {}", path.display()))?
+        .len();
+    if len <= MAX_HISTORY_BYTES {
+        return Ok(());
+    }
+
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to read usage history file {}", path.display()))?;
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .collect::<std::io::Result<_>>()
+        .context("Failed to read usage history line")?;
+
+    let start = lines.len().saturating_sub(RETAIN_SAMPLES);
+    let trimmed = lines[start..].join("\n");
+    std::fs::write(path, format!("{}\n", trimmed))
+        .with_context(|| format!("Failed to trim usage history file {}", path.display()))?;
+    Ok(())
+}
+
+/// Loads every recorded sample for `window`, oldest first.
+pub fn load_samples(window: WindowKind) -> Result<Vec<Sample>> {
+    let path = history_path()?;
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read usage history file {}", path.display()));
+        }
+    };
+
+    let mut samples = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.context("Failed to read usage history line")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Skip malformed lines rather than failing the whole load; history is
+        // a best-effort aid, not a source of truth.
+        if let Ok(sample) = serde_json::from_str::<Sample>(&line) {
+            if sample.window == window {
+                samples.push(sample);
+            }
+        }
+    }
+    Ok(samples)
+}
+
+fn history_path() -> Result<PathBuf> {
+    let codex_home = std::env::var("CODEX_HOME")
+        .ok()
+        .filter(|s| !s.trim().is_empty());
+
+    let base = if let Some(home) = codex_home {
+        PathBuf::from(home)
+    } else {
+        dirs::home_dir()
+            .context("Could not determine home directory")?
+            .join(".codex")
+    };
+
+    Ok(base.join("usage_history.jsonl"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("codex_usage_history_test_{}_{}.jsonl", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_prune_if_large_trims_to_the_newest_retain_samples() {
+        let path = temp_path("prune");
+        let _ = std::fs::remove_file(&path);
+
+        // Enough lines, each long enough, that the file comfortably exceeds
+        // `MAX_HISTORY_BYTES` and pruning is actually exercised.
+        let total_lines = RETAIN_SAMPLES + 20_000;
+        let mut contents = String::new();
+        for i in 0..total_lines {
+            contents.push_str(&format!("{}{}\n", "x".repeat(50), i));
+        }
+        std::fs::write(&path, &contents).unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() > MAX_HISTORY_BYTES);
+
+        prune_if_large(&path).unwrap();
+
+        let trimmed = std::fs::read_to_string(&path).unwrap();
+        let trimmed_lines: Vec<&str> = trimmed.lines().collect();
+        assert_eq!(trimmed_lines.len(), RETAIN_SAMPLES);
+
+        // An off-by-one in `lines[start..]` would shift this window by one
+        // sample in either direction, so pin down both ends.
+        assert_eq!(trimmed_lines.first().unwrap(), &format!("{}{}", "x".repeat(50), total_lines - RETAIN_SAMPLES));
+        assert_eq!(trimmed_lines.last().unwrap(), &format!("{}{}", "x".repeat(50), total_lines - 1));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_prune_if_large_is_a_no_op_under_the_threshold() {
+        let path = temp_path("no_prune");
+        let _ = std::fs::remove_file(&path);
+        std::fs::write(&path, "short\n").unwrap();
+
+        prune_if_large(&path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "short\n");
+        std::fs::remove_file(&path).ok();
+    }
+}
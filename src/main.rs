@@ -1,11 +1,88 @@
 mod api;
 mod auth;
+#[cfg(feature = "discord")]
+mod discord;
 mod display;
+mod history;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod output;
 mod pace;
+mod theme;
+mod tui;
+mod watch;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use colored::Colorize;
 
+struct Args {
+    watch: bool,
+    no_tui: bool,
+    interval: std::time::Duration,
+    webhook: Option<String>,
+    desktop_notify: bool,
+    discord_client_id: Option<String>,
+    metrics_addr: Option<String>,
+    format: Option<output::OutputFormat>,
+    no_hyperlinks: bool,
+}
+
+impl Args {
+    fn parse() -> Result<Self> {
+        let mut watch = false;
+        let mut no_tui = false;
+        let mut interval = std::time::Duration::from_secs(15 * 60);
+        let mut webhook = None;
+        let mut desktop_notify = false;
+        let mut discord_client_id = None;
+        let mut metrics_addr = None;
+        let mut format = None;
+        let mut no_hyperlinks = false;
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--watch" => watch = true,
+                "--no-tui" => no_tui = true,
+                "--interval" => {
+                    let spec = args.next().ok_or_else(|| anyhow!("--interval requires a value"))?;
+                    interval = watch::parse_interval(&spec)?;
+                }
+                "--webhook" => {
+                    webhook = Some(args.next().ok_or_else(|| anyhow!("--webhook requires a value"))?);
+                }
+                "--desktop-notify" => desktop_notify = true,
+                "--discord" => {
+                    discord_client_id =
+                        Some(args.next().ok_or_else(|| anyhow!("--discord requires a client id"))?);
+                }
+                "--serve-metrics" => {
+                    metrics_addr =
+                        Some(args.next().ok_or_else(|| anyhow!("--serve-metrics requires an address"))?);
+                }
+                "--format" => {
+                    let spec = args.next().ok_or_else(|| anyhow!("--format requires a value"))?;
+                    format = Some(output::parse_format(&spec)?);
+                }
+                "--no-hyperlinks" => no_hyperlinks = true,
+                other => return Err(anyhow!("Unrecognized argument: {}", other)),
+            }
+        }
+
+        Ok(Self {
+            watch,
+            no_tui,
+            interval,
+            webhook,
+            desktop_notify,
+            discord_client_id,
+            metrics_addr,
+            format,
+            no_hyperlinks,
+        })
+    }
+}
+
 #[tokio::main]
 async fn main() {
     if let Err(e) = run().await {
@@ -15,15 +92,83 @@ async fn main() {
 }
 
 async fn run() -> Result<()> {
+    let args = Args::parse()?;
+
     // Load credentials
-    let credentials = auth::load_credentials()?;
+    let mut credentials = auth::load_credentials()?;
+
+    #[cfg(feature = "metrics")]
+    if let Some(addr) = args.metrics_addr {
+        return metrics::serve(credentials, &addr, args.interval).await;
+    }
+    #[cfg(not(feature = "metrics"))]
+    if args.metrics_addr.is_some() {
+        return Err(anyhow!("codex-usage was built without the `metrics` feature"));
+    }
+
+    #[cfg(not(feature = "discord"))]
+    if args.discord_client_id.is_some() {
+        return Err(anyhow!("codex-usage was built without the `discord` feature"));
+    }
+
+    if args.watch {
+        let mut sinks: Vec<Box<dyn watch::AlertSink>> = Vec::new();
+        if let Some(url) = args.webhook {
+            sinks.push(Box::new(watch::WebhookSink::new(url)));
+        }
+        if args.desktop_notify {
+            sinks.push(Box::new(watch::DesktopSink));
+        }
+
+        if args.no_tui {
+            return watch::watch(
+                credentials,
+                args.interval,
+                &sinks,
+                args.discord_client_id.as_deref(),
+                args.no_hyperlinks,
+            )
+            .await;
+        }
+        return tui::run(credentials, args.interval, &sinks, args.discord_client_id.as_deref()).await;
+    }
 
     // Fetch usage
     let fetcher = api::UsageFetcher::new();
-    let response = fetcher.fetch_usage(&credentials).await?;
+    let response = fetcher.fetch_usage(&mut credentials).await?;
 
-    // Display usage
-    display::display_usage(&response);
+    // Record this fetch so pace can be computed from observed history.
+    record_history(&response);
+
+    if let Some(format) = args.format {
+        println!("{}", output::render(&response, format)?);
+    } else {
+        display::display_usage(&response, args.no_hyperlinks);
+    }
 
     Ok(())
 }
+
+pub(crate) fn record_history(response: &api::UsageResponse) {
+    let Some(rate_limit) = &response.rate_limit else {
+        return;
+    };
+    let timestamp = chrono::Utc::now().timestamp();
+
+    let windows = [
+        (history::WindowKind::Primary, &rate_limit.primary_window),
+        (history::WindowKind::Secondary, &rate_limit.secondary_window),
+    ];
+    for (kind, window) in windows {
+        let Some(window) = window else { continue };
+        let sample = history::Sample {
+            timestamp,
+            window: kind,
+            used_percent: window.used_percent,
+            reset_at: window.reset_at,
+            limit_window_seconds: window.limit_window_seconds,
+        };
+        // Best-effort: a failure to persist history shouldn't block display.
+        let _ = history::record_sample(&sample);
+    }
+}
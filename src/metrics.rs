@@ -0,0 +1,136 @@
+//! Optional Prometheus `/metrics` exporter, enabled via the `metrics` cargo
+//! feature so the base CLI stays dependency-light.
+#![cfg(feature = "metrics")]
+
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::api::{UsageFetcher, UsageResponse};
+use crate::auth::Credentials;
+use crate::pace::UsagePace;
+
+struct Snapshot {
+    response: UsageResponse,
+}
+
+/// Serves Prometheus text-format metrics on `addr`, re-fetching usage on
+/// `interval` and caching the latest snapshot for scrapes in between.
+pub async fn serve(mut credentials: Credentials, addr: &str, interval: Duration) -> Result<()> {
+    let fetcher = UsageFetcher::new();
+    let initial = fetcher.fetch_usage(&mut credentials).await?;
+    crate::record_history(&initial);
+    let snapshot = Arc::new(RwLock::new(Snapshot { response: initial }));
+
+    let refresh_snapshot = snapshot.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            if let Ok(response) = fetcher.fetch_usage(&mut credentials).await {
+                crate::record_history(&response);
+                if let Ok(mut guard) = refresh_snapshot.write() {
+                    guard.response = response;
+                }
+            }
+        }
+    });
+
+    let server = tiny_http::Server::http(addr)
+        .map_err(|e| anyhow::anyhow!("Failed to bind metrics server on {}: {}", addr, e))?;
+
+    // `incoming_requests()` blocks the calling thread between requests;
+    // running it on a blocking-pool thread keeps it from parking a tokio
+    // worker and starving the periodic refresh task spawned above.
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        for request in server.incoming_requests() {
+            let body = {
+                let guard = snapshot
+                    .read()
+                    .map_err(|_| anyhow::anyhow!("Metrics snapshot lock poisoned"))?;
+                render_prometheus(&guard.response)
+            };
+            let response = tiny_http::Response::from_string(body).with_header(
+                tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
+                    .expect("static header is valid"),
+            );
+            let _ = request.respond(response);
+        }
+        Ok(())
+    })
+    .await
+    .context("Metrics server task panicked")??;
+
+    Ok(())
+}
+
+fn render_prometheus(response: &UsageResponse) -> String {
+    let mut out = String::new();
+    let plan_label = response
+        .plan_type
+        .as_ref()
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    if let Some(rate_limit) = &response.rate_limit {
+        write_window_metrics(&mut out, "primary", &rate_limit.primary_window, &plan_label, 300);
+        write_window_metrics(&mut out, "secondary", &rate_limit.secondary_window, &plan_label, 10080);
+    }
+
+    if let Some(credits) = &response.credits {
+        if let Some(balance) = credits.balance {
+            let _ = writeln!(
+                out,
+                "codex_credits_balance{{plan_type=\"{}\"}} {}",
+                plan_label, balance
+            );
+        }
+    }
+
+    out
+}
+
+fn write_window_metrics(
+    out: &mut String,
+    window: &str,
+    snapshot: &Option<crate::api::WindowSnapshot>,
+    plan_label: &str,
+    default_window_minutes: i64,
+) {
+    let Some(snapshot) = snapshot else { return };
+
+    let _ = writeln!(
+        out,
+        "codex_usage_used_percent{{window=\"{}\",plan_type=\"{}\"}} {}",
+        window, plan_label, snapshot.used_percent
+    );
+
+    let Some(pace) = UsagePace::from_window(snapshot, chrono::Utc::now(), default_window_minutes) else {
+        return;
+    };
+
+    let _ = writeln!(
+        out,
+        "codex_usage_expected_percent{{window=\"{}\",plan_type=\"{}\"}} {}",
+        window, plan_label, pace.expected_used_percent
+    );
+    let _ = writeln!(
+        out,
+        "codex_usage_delta_percent{{window=\"{}\",plan_type=\"{}\"}} {}",
+        window, plan_label, pace.delta_percent
+    );
+    if let Some(eta) = pace.eta_seconds {
+        let _ = writeln!(
+            out,
+            "codex_usage_eta_seconds{{window=\"{}\",plan_type=\"{}\"}} {}",
+            window, plan_label, eta
+        );
+    }
+    let _ = writeln!(
+        out,
+        "codex_usage_will_last_to_reset{{window=\"{}\",plan_type=\"{}\"}} {}",
+        window,
+        plan_label,
+        if pace.will_last_to_reset { 1 } else { 0 }
+    );
+}
@@ -0,0 +1,265 @@
+use crate::api::{UsageResponse, WindowSnapshot};
+use crate::history::{self, WindowKind};
+use crate::pace::UsagePace;
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+
+/// Machine-readable rendering of a [`UsageResponse`], selected with `--format`
+/// for scripting and prompt-integration use cases that can't parse the
+/// colored terminal output from [`crate::display`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Prompt,
+}
+
+pub fn parse_format(spec: &str) -> Result<OutputFormat> {
+    match spec {
+        "json" => Ok(OutputFormat::Json),
+        "csv" => Ok(OutputFormat::Csv),
+        "prompt" => Ok(OutputFormat::Prompt),
+        other => Err(anyhow!(
+            "Unrecognized format '{}': expected json, csv, or prompt",
+            other
+        )),
+    }
+}
+
+pub fn render(response: &UsageResponse, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => render_json(response),
+        OutputFormat::Csv => Ok(render_csv(response)),
+        OutputFormat::Prompt => Ok(render_prompt(response)),
+    }
+}
+
+#[derive(Serialize)]
+struct WindowRow {
+    used_percent: i64,
+    reset_at: i64,
+    stage: Option<&'static str>,
+    delta_percent: Option<f64>,
+    eta_seconds: Option<f64>,
+    will_last_to_reset: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct JsonOutput {
+    plan_type: Option<String>,
+    credits_balance: Option<f64>,
+    primary_window: Option<WindowRow>,
+    secondary_window: Option<WindowRow>,
+}
+
+/// Computes pace for `window`, preferring the history-regression-backed
+/// `from_history` and falling back to the single-point `from_window` before
+/// any history exists for `kind`.
+fn pace_for(window: &WindowSnapshot, kind: WindowKind, default_window_minutes: i64) -> Option<UsagePace> {
+    let now = chrono::Utc::now();
+    let samples = history::load_samples(kind).unwrap_or_default();
+    UsagePace::from_history(&samples, now, default_window_minutes)
+        .or_else(|| UsagePace::from_window(window, now, default_window_minutes))
+}
+
+fn window_row(window: &WindowSnapshot, kind: WindowKind, default_window_minutes: i64) -> WindowRow {
+    let pace = pace_for(window, kind, default_window_minutes);
+    WindowRow {
+        used_percent: window.used_percent,
+        reset_at: window.reset_at,
+        stage: pace.as_ref().map(|p| p.stage_description()),
+        delta_percent: pace.as_ref().map(|p| p.delta_percent),
+        eta_seconds: pace.as_ref().and_then(|p| p.eta_seconds),
+        will_last_to_reset: pace.as_ref().map(|p| p.will_last_to_reset),
+    }
+}
+
+fn render_json(response: &UsageResponse) -> Result<String> {
+    let rate_limit = &response.rate_limit;
+    let primary_window = rate_limit
+        .as_ref()
+        .and_then(|r| r.primary_window.as_ref())
+        .map(|w| window_row(w, WindowKind::Primary, 300));
+    let secondary_window = rate_limit
+        .as_ref()
+        .and_then(|r| r.secondary_window.as_ref())
+        .map(|w| window_row(w, WindowKind::Secondary, 10080));
+
+    let output = JsonOutput {
+        plan_type: response.plan_type.as_ref().map(|p| p.to_string()),
+        credits_balance: response.credits.as_ref().and_then(|c| c.balance),
+        primary_window,
+        secondary_window,
+    };
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+const CSV_HEADER: &str = "plan_type,credits_balance,\
+primary_used_percent,primary_remaining_percent,primary_reset_at,primary_stage,primary_delta_percent,primary_eta_seconds,primary_will_last_to_reset,\
+secondary_used_percent,secondary_remaining_percent,secondary_reset_at,secondary_stage,secondary_delta_percent,secondary_eta_seconds,secondary_will_last_to_reset";
+
+/// Renders a single CSV data row (header + one row) covering everything a
+/// scripting consumer needs in one parse: plan, credits, and both windows'
+/// used/remaining percent, reset time, stage, delta, ETA, and whether the
+/// window is projected to last to reset.
+fn render_csv(response: &UsageResponse) -> String {
+    let plan_type = response
+        .plan_type
+        .as_ref()
+        .map(|p| p.to_string())
+        .unwrap_or_default();
+    let credits_balance = response
+        .credits
+        .as_ref()
+        .and_then(|c| c.balance)
+        .map(|b| format!("{:.2}", b))
+        .unwrap_or_default();
+
+    let primary = response
+        .rate_limit
+        .as_ref()
+        .and_then(|r| r.primary_window.as_ref())
+        .map(|w| window_row(w, WindowKind::Primary, 300));
+    let secondary = response
+        .rate_limit
+        .as_ref()
+        .and_then(|r| r.secondary_window.as_ref())
+        .map(|w| window_row(w, WindowKind::Secondary, 10080));
+
+    let row = format!(
+        "{},{},{},{}",
+        plan_type,
+        credits_balance,
+        csv_window_fields(primary.as_ref()),
+        csv_window_fields(secondary.as_ref()),
+    );
+
+    format!("{}\n{}", CSV_HEADER, row)
+}
+
+/// The 7 per-window fields (`used_percent,remaining_percent,reset_at,stage,
+/// delta_percent,eta_seconds,will_last_to_reset`), empty when `row` is `None`
+/// so the row's column count stays fixed regardless of which windows the API
+/// returned.
+fn csv_window_fields(row: Option<&WindowRow>) -> String {
+    match row {
+        Some(row) => format!(
+            "{},{},{},{},{},{},{}",
+            row.used_percent,
+            100 - row.used_percent.clamp(0, 100),
+            row.reset_at,
+            row.stage.unwrap_or(""),
+            row.delta_percent
+                .map(|d| format!("{:.1}", d))
+                .unwrap_or_default(),
+            row.eta_seconds
+                .map(|e| format!("{:.0}", e))
+                .unwrap_or_default(),
+            row.will_last_to_reset
+                .map(|b| b.to_string())
+                .unwrap_or_default(),
+        ),
+        None => ",,,,,,".to_string(),
+    }
+}
+
+fn render_prompt(response: &UsageResponse) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut worst: Option<(f64, &'static str)> = None;
+
+    if let Some(rate_limit) = &response.rate_limit {
+        if let Some(window) = &rate_limit.primary_window {
+            parts.push(format!("5h {}%", window.used_percent));
+            track_worst(&mut worst, pace_for(window, WindowKind::Primary, 300).as_ref());
+        }
+        if let Some(window) = &rate_limit.secondary_window {
+            parts.push(format!("wk {}%", window.used_percent));
+            track_worst(&mut worst, pace_for(window, WindowKind::Secondary, 10080).as_ref());
+        }
+    }
+
+    if parts.is_empty() {
+        return "no usage data".to_string();
+    }
+
+    if let Some((_, stage)) = worst {
+        parts.push(stage.to_string());
+    }
+    parts.join(" · ")
+}
+
+/// Tracks whichever window's stage is furthest off-pace (largest `|delta|`),
+/// so the one-line summary surfaces the window most deserving of attention.
+fn track_worst(worst: &mut Option<(f64, &'static str)>, pace: Option<&UsagePace>) {
+    let Some(pace) = pace else { return };
+    let abs_delta = pace.delta_percent.abs();
+    let is_worse = match worst {
+        Some((current, _)) => abs_delta > *current,
+        None => true,
+    };
+    if is_worse {
+        *worst = Some((abs_delta, pace.stage_description()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_response(primary_used: i64, primary_reset_at: i64) -> UsageResponse {
+        let json = format!(
+            r#"{{
+                "plan_type": "plus",
+                "rate_limit": {{
+                    "primary_window": {{
+                        "used_percent": {primary_used},
+                        "reset_at": {primary_reset_at},
+                        "limit_window_seconds": 18000
+                    }}
+                }},
+                "credits": {{
+                    "has_credits": true,
+                    "unlimited": false,
+                    "balance": 12.5
+                }}
+            }}"#,
+        );
+        serde_json::from_str(&json).expect("sample JSON should deserialize")
+    }
+
+    #[test]
+    fn test_render_csv_is_a_single_row_with_plan_and_credits() {
+        let now = chrono::Utc::now().timestamp();
+        let response = sample_response(50, now + 3600);
+        let csv = render_csv(&response);
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), CSV_HEADER);
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("plus,12.50,"));
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn test_render_csv_leaves_missing_secondary_window_fields_empty() {
+        let now = chrono::Utc::now().timestamp();
+        let response = sample_response(10, now + 3600);
+        let csv = render_csv(&response);
+
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.ends_with(",,,,,,"), "row was: {row}");
+    }
+
+    #[test]
+    fn test_render_json_omits_stage_when_pace_is_unavailable() {
+        // A reset_at already in the past makes `UsagePace::from_window`
+        // return `None`. `pace_for` tries `from_history` first, which reads
+        // recorded samples rather than this response, so this assumes no
+        // live usage history for the primary window.
+        let now = chrono::Utc::now().timestamp();
+        let response = sample_response(50, now - 10);
+        let json = render(&response, OutputFormat::Json).unwrap();
+        assert!(json.contains("\"stage\": null"), "json was: {json}");
+    }
+}
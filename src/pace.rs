@@ -1,4 +1,5 @@
 use crate::api::WindowSnapshot;
+use crate::history::Sample;
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,9 +27,7 @@ pub struct UsagePace {
 impl UsagePace {
     pub fn from_window(window: &WindowSnapshot, now: DateTime<Utc>, default_window_minutes: i64) -> Option<Self> {
         let reset_time = DateTime::from_timestamp(window.reset_at, 0)?;
-        let window_minutes = (window.limit_window_seconds / 60) as i64;
-        let window_minutes = if window_minutes > 0 { window_minutes } else { default_window_minutes };
-
+        let window_minutes = Self::window_minutes(window.limit_window_seconds, default_window_minutes);
         let duration_sec = window_minutes as f64 * 60.0;
         let time_until_reset = (reset_time - now).num_seconds().max(0) as f64;
 
@@ -37,19 +36,143 @@ impl UsagePace {
         }
 
         let elapsed = (duration_sec - time_until_reset).clamp(0.0, duration_sec);
-        let expected = (elapsed / duration_sec * 100.0).clamp(0.0, 100.0);
         let actual = (window.used_percent as f64).clamp(0.0, 100.0);
 
         if elapsed == 0.0 && actual > 0.0 {
             return None;
         }
 
+        let rate = if elapsed > 0.0 { Some(actual / elapsed) } else { None };
+        Self::build(elapsed, duration_sec, time_until_reset, actual, rate)
+    }
+
+    /// Like `from_window`, but derives the burn rate from a least-squares fit
+    /// over `samples` (`used_percent = a + b·t`) instead of a single-point
+    /// average, which is far steadier once a window has a few observations.
+    pub fn from_history(
+        samples: &[Sample],
+        now: DateTime<Utc>,
+        default_window_minutes: i64,
+    ) -> Option<Self> {
+        let latest = samples.last()?;
+        let reset_time = DateTime::from_timestamp(latest.reset_at, 0)?;
+        let window_minutes = Self::window_minutes(latest.limit_window_seconds, default_window_minutes);
+        let duration_sec = window_minutes as f64 * 60.0;
+        let time_until_reset = (reset_time - now).num_seconds().max(0) as f64;
+
+        if time_until_reset > duration_sec || time_until_reset == 0.0 {
+            return None;
+        }
+
+        let elapsed = (duration_sec - time_until_reset).clamp(0.0, duration_sec);
+        let actual = (latest.used_percent as f64).clamp(0.0, 100.0);
+
+        if elapsed == 0.0 && actual > 0.0 {
+            return None;
+        }
+
+        let segment = Self::current_segment(samples, latest.reset_at);
+        let rate = Self::fit_slope(&segment)
+            .or(if elapsed > 0.0 { Some(actual / elapsed) } else { None });
+
+        Self::build(elapsed, duration_sec, time_until_reset, actual, rate)
+    }
+
+    /// Like `from_history`, but only succeeds when there are enough in-window
+    /// samples for the regression to be trustworthy (no single-point
+    /// fallback). Used to show a measured ETA *alongside* the ETA of a
+    /// separately computed pace, rather than replacing it.
+    pub fn observed_eta(samples: &[Sample], now: DateTime<Utc>, default_window_minutes: i64) -> Option<Self> {
+        let latest = samples.last()?;
+        let reset_time = DateTime::from_timestamp(latest.reset_at, 0)?;
+        let window_minutes = Self::window_minutes(latest.limit_window_seconds, default_window_minutes);
+        let duration_sec = window_minutes as f64 * 60.0;
+        let time_until_reset = (reset_time - now).num_seconds().max(0) as f64;
+
+        if time_until_reset > duration_sec || time_until_reset == 0.0 {
+            return None;
+        }
+
+        let elapsed = (duration_sec - time_until_reset).clamp(0.0, duration_sec);
+        let actual = (latest.used_percent as f64).clamp(0.0, 100.0);
+
+        if elapsed == 0.0 && actual > 0.0 {
+            return None;
+        }
+
+        let segment = Self::current_segment(samples, latest.reset_at);
+        let rate = Self::fit_slope(&segment)?;
+
+        Self::build(elapsed, duration_sec, time_until_reset, actual, Some(rate))
+    }
+
+    fn window_minutes(limit_window_seconds: i64, default_window_minutes: i64) -> i64 {
+        let minutes = limit_window_seconds / 60;
+        if minutes > 0 { minutes } else { default_window_minutes }
+    }
+
+    /// Walks backward from the newest sample, stopping at a window reset
+    /// (`reset_at` changing, or `used_percent` dropping versus the next-newer
+    /// sample) so pre- and post-reset points never end up in one regression.
+    fn current_segment(samples: &[Sample], reset_at: i64) -> Vec<&Sample> {
+        let mut segment: Vec<&Sample> = Vec::new();
+        for sample in samples.iter().rev() {
+            if sample.reset_at != reset_at {
+                break;
+            }
+            if let Some(&newest) = segment.last() {
+                if sample.used_percent > newest.used_percent {
+                    break;
+                }
+            }
+            segment.push(sample);
+        }
+        segment.reverse();
+        segment
+    }
+
+    /// Least-squares slope `b` (percent/second) of `used_percent = a + b·t`
+    /// over `segment`. Requires at least two samples to be trustworthy.
+    fn fit_slope(segment: &[&Sample]) -> Option<f64> {
+        if segment.len() < 2 {
+            return None;
+        }
+
+        let n = segment.len() as f64;
+        let t0 = segment[0].timestamp as f64;
+        let xs: Vec<f64> = segment.iter().map(|s| s.timestamp as f64 - t0).collect();
+        let ys: Vec<f64> = segment.iter().map(|s| s.used_percent as f64).collect();
+
+        let mean_x = xs.iter().sum::<f64>() / n;
+        let mean_y = ys.iter().sum::<f64>() / n;
+
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            numerator += (x - mean_x) * (y - mean_y);
+            denominator += (x - mean_x).powi(2);
+        }
+
+        if denominator == 0.0 {
+            return None;
+        }
+
+        Some(numerator / denominator)
+    }
+
+    fn build(
+        elapsed: f64,
+        duration_sec: f64,
+        time_until_reset: f64,
+        actual: f64,
+        rate: Option<f64>,
+    ) -> Option<Self> {
+        let expected = (elapsed / duration_sec * 100.0).clamp(0.0, 100.0);
         let delta = actual - expected;
         let stage = Self::stage_from_delta(delta);
 
-        let (eta_seconds, will_last_to_reset) = if elapsed > 0.0 && actual > 0.0 {
-            let rate = actual / elapsed;
-            if rate > 0.0 {
+        let (eta_seconds, will_last_to_reset) = match rate {
+            Some(rate) if rate > 0.0 => {
                 let remaining = (100.0 - actual).max(0.0);
                 let candidate = remaining / rate;
                 if candidate >= time_until_reset {
@@ -57,13 +180,11 @@ impl UsagePace {
                 } else {
                     (Some(candidate), false)
                 }
-            } else {
-                (None, true)
             }
-        } else if elapsed > 0.0 && actual == 0.0 {
-            (None, true)
-        } else {
-            (None, false)
+            // Non-positive slope: usage isn't climbing, so it will last.
+            Some(_) => (None, true),
+            None if elapsed > 0.0 && actual == 0.0 => (None, true),
+            None => (None, false),
         };
 
         Some(UsagePace {
@@ -166,4 +287,42 @@ mod tests {
         assert_eq!(UsagePace::stage_from_delta(20.0), Stage::FarAhead);
         assert_eq!(UsagePace::stage_from_delta(-20.0), Stage::FarBehind);
     }
+
+    fn sample(timestamp: i64, used_percent: i64, reset_at: i64) -> Sample {
+        Sample {
+            timestamp,
+            window: crate::history::WindowKind::Primary,
+            used_percent,
+            reset_at,
+            limit_window_seconds: 18000,
+        }
+    }
+
+    #[test]
+    fn test_fit_slope_requires_two_samples() {
+        let one = [sample(0, 10, 1000)];
+        let refs: Vec<&Sample> = one.iter().collect();
+        assert_eq!(UsagePace::fit_slope(&refs), None);
+    }
+
+    #[test]
+    fn test_fit_slope_matches_linear_series() {
+        let samples = [sample(0, 10, 1000), sample(100, 20, 1000), sample(200, 30, 1000)];
+        let refs: Vec<&Sample> = samples.iter().collect();
+        let slope = UsagePace::fit_slope(&refs).unwrap();
+        assert!((slope - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_current_segment_stops_at_reset() {
+        let samples = vec![
+            sample(0, 80, 1000),  // previous window, about to reset
+            sample(50, 5, 2000),  // reset happened, fresh segment
+            sample(150, 15, 2000),
+        ];
+        let segment = UsagePace::current_segment(&samples, 2000);
+        assert_eq!(segment.len(), 2);
+        assert_eq!(segment[0].timestamp, 50);
+        assert_eq!(segment[1].timestamp, 150);
+    }
 }
@@ -0,0 +1,121 @@
+use std::io::{IsTerminal, Read, Write};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+
+/// Which background family the terminal is running on, used to keep the
+/// empty-bar glyph and dimmed text readable on both light and dark themes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    Dark,
+    Light,
+}
+
+static PALETTE: OnceLock<Palette> = OnceLock::new();
+
+/// Detects the terminal's background: an OSC 11 query first, then the
+/// `COLORFGBG` env var, defaulting to `Dark` if neither is available.
+///
+/// The result is cached for the life of the process: the background can't
+/// change mid-run, and an OSC 11 query spawns a thread blocked on stdin that
+/// only returns (or leaks, on terminals that never reply) once — redoing it
+/// on every redraw of a long-running `--watch` session leaks one thread per
+/// tick.
+pub fn detect_palette() -> Palette {
+    *PALETTE.get_or_init(|| {
+        if let Some((r, g, b)) = query_background_rgb() {
+            return if relative_luminance(r, g, b) > 0.5 { Palette::Light } else { Palette::Dark };
+        }
+
+        if let Some(palette) = palette_from_colorfgbg() {
+            return palette;
+        }
+
+        Palette::Dark
+    })
+}
+
+fn palette_from_colorfgbg() -> Option<Palette> {
+    parse_colorfgbg(&std::env::var("COLORFGBG").ok()?)
+}
+
+fn parse_colorfgbg(colorfgbg: &str) -> Option<Palette> {
+    let bg = colorfgbg.split(';').next_back()?.trim().parse::<u8>().ok()?;
+    // By convention, background color codes 7 and 15 are light; everything
+    // else is treated as dark.
+    Some(if matches!(bg, 7 | 15) { Palette::Light } else { Palette::Dark })
+}
+
+/// Emits the OSC 11 background-color query and parses the terminal's
+/// `rgb:RRRR/GGGG/BBBB` reply, with a short timeout in case the terminal
+/// doesn't support it (or we're not attached to a real tty).
+fn query_background_rgb() -> Option<(u8, u8, u8)> {
+    if !std::io::stdout().is_terminal() || !std::io::stdin().is_terminal() {
+        return None;
+    }
+
+    crossterm::terminal::enable_raw_mode().ok()?;
+    let result = (|| {
+        let mut stdout = std::io::stdout();
+        write!(stdout, "\x1b]11;?\x07").ok()?;
+        stdout.flush().ok()?;
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 64];
+            if let Ok(n) = std::io::stdin().read(&mut buf) {
+                let _ = tx.send(buf[..n].to_vec());
+            }
+        });
+
+        let bytes = rx.recv_timeout(Duration::from_millis(200)).ok()?;
+        parse_osc11_response(&String::from_utf8_lossy(&bytes))
+    })();
+    crossterm::terminal::disable_raw_mode().ok();
+
+    result
+}
+
+fn parse_osc11_response(s: &str) -> Option<(u8, u8, u8)> {
+    let rest = &s[s.find("rgb:")? + 4..];
+    let end = rest.find(['\u{7}', '\u{1b}']).unwrap_or(rest.len());
+    let mut parts = rest[..end].split('/');
+
+    let component = |hex: &str| -> Option<u8> {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = (1u32 << (4 * hex.len())) - 1;
+        Some(((value as f64 / max as f64) * 255.0).round() as u8)
+    };
+
+    Some((component(parts.next()?)?, component(parts.next()?)?, component(parts.next()?)?))
+}
+
+fn relative_luminance(r: u8, g: u8, b: u8) -> f64 {
+    let normalize = |c: u8| c as f64 / 255.0;
+    0.2126 * normalize(r) + 0.7152 * normalize(g) + 0.0722 * normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_osc11_response() {
+        assert_eq!(parse_osc11_response("\u{1b}]11;rgb:ffff/ffff/ffff\u{7}"), Some((255, 255, 255)));
+        assert_eq!(parse_osc11_response("\u{1b}]11;rgb:0000/0000/0000\u{7}"), Some((0, 0, 0)));
+        assert_eq!(parse_osc11_response("garbage"), None);
+    }
+
+    #[test]
+    fn test_relative_luminance_thresholds() {
+        assert!(relative_luminance(255, 255, 255) > 0.5);
+        assert!(relative_luminance(0, 0, 0) < 0.5);
+    }
+
+    #[test]
+    fn test_parse_colorfgbg() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Palette::Dark));
+        assert_eq!(parse_colorfgbg("0;15"), Some(Palette::Light));
+        assert_eq!(parse_colorfgbg("0;7"), Some(Palette::Light));
+        assert_eq!(parse_colorfgbg("nonsense"), None);
+    }
+}
@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph};
+use std::io::Stdout;
+use std::time::{Duration, Instant};
+
+use crate::api::{UsageFetcher, UsageResponse, WindowSnapshot, format_reset_time};
+use crate::auth::Credentials;
+use crate::watch::{self, AlertSink};
+
+/// Tracks "monitoring for HH:MM:SS", frozen while paused. Elapsed time is
+/// `cumulative + last_start.elapsed()` while running, and just `cumulative`
+/// while paused — mirroring bandwhich's header-details pattern.
+struct ElapsedClock {
+    cumulative: Duration,
+    last_start: Option<Instant>,
+}
+
+impl ElapsedClock {
+    fn new() -> Self {
+        Self { cumulative: Duration::ZERO, last_start: Some(Instant::now()) }
+    }
+
+    fn elapsed(&self) -> Duration {
+        match self.last_start {
+            Some(start) => self.cumulative + start.elapsed(),
+            None => self.cumulative,
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        match self.last_start.take() {
+            Some(start) => self.cumulative += start.elapsed(),
+            None => self.last_start = Some(Instant::now()),
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.last_start.is_none()
+    }
+}
+
+fn format_elapsed(d: Duration) -> String {
+    let secs = d.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
+
+/// Installs a panic hook that restores the terminal (disables raw mode,
+/// leaves the alternate screen) before the default hook runs, so a crash
+/// mid-dashboard doesn't leave the user's terminal garbled.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(std::io::stdout(), LeaveAlternateScreen);
+        default_hook(info);
+    }));
+}
+
+/// Runs the full-screen live dashboard: re-polls `fetch_usage` on `interval`
+/// and redraws the window bars and pace line in place. `p` pauses/resumes
+/// the elapsed-time counter, `r` forces an immediate refresh, `q` quits.
+pub async fn run(
+    mut credentials: Credentials,
+    interval: Duration,
+    sinks: &[Box<dyn AlertSink>],
+    discord_client_id: Option<&str>,
+) -> Result<()> {
+    install_panic_hook();
+
+    enable_raw_mode().context("Failed to enable raw mode")?;
+    let mut stdout = std::io::stdout();
+    execute!(stdout, EnterAlternateScreen).context("Failed to enter the alternate screen")?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend).context("Failed to initialize the terminal")?;
+
+    let result = run_loop(&mut terminal, &mut credentials, interval, sinks, discord_client_id).await;
+
+    disable_raw_mode().ok();
+    execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
+
+    result
+}
+
+async fn run_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    credentials: &mut Credentials,
+    interval: Duration,
+    sinks: &[Box<dyn AlertSink>],
+    discord_client_id: Option<&str>,
+) -> Result<()> {
+    let fetcher = UsageFetcher::new();
+    let mut clock = ElapsedClock::new();
+    let mut last_alerted = false;
+
+    #[cfg(feature = "discord")]
+    let mut discord = discord_client_id.and_then(|id| {
+        tokio::task::block_in_place(|| crate::discord::DiscordPresence::connect(id).ok())
+    });
+    #[cfg(not(feature = "discord"))]
+    let _ = discord_client_id;
+
+    let mut response = fetcher.fetch_usage(&mut *credentials).await?;
+    crate::record_history(&response);
+    let mut last_poll = Instant::now();
+
+    loop {
+        terminal.draw(|frame| draw(frame, &response, &clock))?;
+
+        let poll_timeout = interval
+            .saturating_sub(last_poll.elapsed())
+            .min(Duration::from_millis(200));
+        if event::poll(poll_timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    match key.code {
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('p') => clock.toggle_pause(),
+                        KeyCode::Char('r') => last_poll = Instant::now() - interval,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        if last_poll.elapsed() >= interval {
+            response = fetcher.fetch_usage(&mut *credentials).await?;
+            crate::record_history(&response);
+            last_poll = Instant::now();
+
+            if let Some(pace) = watch::pace_from_response(&response) {
+                let alertable = watch::is_alertable(&pace);
+                if alertable && !last_alerted {
+                    // Sinks (e.g. the webhook's blocking HTTP call) are
+                    // synchronous; run them via `block_in_place` so a slow or
+                    // unreachable endpoint can't freeze the live dashboard.
+                    tokio::task::block_in_place(|| {
+                        for sink in sinks {
+                            let _ = sink.send(&pace, &response);
+                        }
+                    });
+                }
+                last_alerted = alertable;
+
+                #[cfg(feature = "discord")]
+                if let Some(presence) = discord.as_mut() {
+                    tokio::task::block_in_place(|| {
+                        let _ = presence.update(&pace);
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(frame: &mut ratatui::Frame, response: &UsageResponse, clock: &ElapsedClock) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ])
+        .split(frame.area());
+
+    let status = if clock.is_paused() { "paused" } else { "monitoring" };
+    let header = Paragraph::new(Line::from(Span::raw(format!(
+        "{} for {}",
+        status,
+        format_elapsed(clock.elapsed())
+    ))))
+    .block(Block::default().borders(Borders::ALL).title("codex-usage"));
+    frame.render_widget(header, chunks[0]);
+
+    if let Some(rate_limit) = &response.rate_limit {
+        if let Some(window) = &rate_limit.primary_window {
+            frame.render_widget(window_gauge(window, "5h Window"), chunks[1]);
+        }
+        if let Some(window) = &rate_limit.secondary_window {
+            frame.render_widget(window_gauge(window, "Weekly Window"), chunks[2]);
+        }
+    }
+
+    if let Some(pace) = watch::pace_from_response(response) {
+        let line = Line::from(vec![
+            Span::raw(format!(
+                "Pace: {} {} ({})   ",
+                pace.stage_emoji(),
+                pace.stage_description(),
+                pace.format_delta()
+            )),
+            Span::raw(format!("ETA: {}", pace.format_eta())),
+        ]);
+        frame.render_widget(
+            Paragraph::new(line).block(Block::default().borders(Borders::ALL).title("Pace")),
+            chunks[3],
+        );
+    }
+
+    let footer = Paragraph::new(Line::from(Span::raw("[p] pause/resume  [r] refresh now  [q] quit")));
+    frame.render_widget(footer, chunks[4]);
+}
+
+fn window_gauge(window: &WindowSnapshot, label: &str) -> Gauge<'static> {
+    let used = window.used_percent.clamp(0, 100) as u16;
+    let color = if used >= 90 {
+        Color::Red
+    } else if used >= 70 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+
+    Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "{} · reset {}",
+            label,
+            format_reset_time(window.reset_at)
+        )))
+        .gauge_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+        .percent(used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_elapsed(Duration::from_secs(3661)), "01:01:01");
+    }
+
+    #[test]
+    fn test_elapsed_clock_pauses() {
+        let mut clock = ElapsedClock::new();
+        assert!(!clock.is_paused());
+        clock.toggle_pause();
+        assert!(clock.is_paused());
+        let frozen = clock.elapsed();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.elapsed(), frozen);
+    }
+}
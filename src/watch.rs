@@ -0,0 +1,200 @@
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::api::{UsageFetcher, UsageResponse};
+use crate::auth::Credentials;
+use crate::display;
+use crate::pace::{Stage, UsagePace};
+
+/// Parses a human-friendly interval: `"15m"`, `"2h"`, `"30s"`, or one of the
+/// named cadences `"hourly"` / `"twice-daily"` / `"daily"`.
+pub fn parse_interval(spec: &str) -> Result<Duration> {
+    let spec = spec.trim();
+
+    match spec.to_lowercase().as_str() {
+        "hourly" => return Ok(Duration::from_secs(60 * 60)),
+        "twice-daily" => return Ok(Duration::from_secs(12 * 60 * 60)),
+        "daily" => return Ok(Duration::from_secs(24 * 60 * 60)),
+        _ => {}
+    }
+
+    let (number, unit) = spec.split_at(
+        spec.find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| anyhow!("Invalid interval '{}': expected a number and unit (e.g. '15m')", spec))?,
+    );
+
+    let number: u64 = number
+        .parse()
+        .with_context(|| format!("Invalid interval '{}': not a number", spec))?;
+
+    let seconds = match unit {
+        "s" => number,
+        "m" => number * 60,
+        "h" => number * 60 * 60,
+        "d" => number * 24 * 60 * 60,
+        other => return Err(anyhow!("Invalid interval '{}': unknown unit '{}'", spec, other)),
+    };
+
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Stages that warrant an alert once entered. Going from a non-alertable
+/// state into one of these (or `will_last_to_reset` flipping to false) fires
+/// a notification; staying alertable, including moving between `Behind` and
+/// `FarBehind`, does not re-fire.
+pub(crate) fn is_alertable(pace: &UsagePace) -> bool {
+    matches!(pace.stage, Stage::Behind | Stage::FarBehind) || !pace.will_last_to_reset
+}
+
+/// Computes pace for the weekly window, falling back to the 5h window when
+/// the weekly one isn't available yet. Shared by the headless watch loop and
+/// the TUI dashboard so both alert on the same condition.
+pub(crate) fn pace_from_response(response: &UsageResponse) -> Option<UsagePace> {
+    let rate_limit = response.rate_limit.as_ref()?;
+    rate_limit
+        .secondary_window
+        .as_ref()
+        .and_then(|w| UsagePace::from_window(w, chrono::Utc::now(), 10080))
+        .or_else(|| {
+            rate_limit
+                .primary_window
+                .as_ref()
+                .and_then(|w| UsagePace::from_window(w, chrono::Utc::now(), 300))
+        })
+}
+
+/// A destination for threshold alerts. Implementations should be cheap to
+/// call on every tick; `watch()` and the TUI only invoke them when the alert
+/// condition turns on.
+pub trait AlertSink {
+    fn send(&self, pace: &UsagePace, response: &UsageResponse) -> Result<()>;
+}
+
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("building the webhook HTTP client should not fail");
+        Self { url, client }
+    }
+}
+
+impl AlertSink for WebhookSink {
+    fn send(&self, pace: &UsagePace, response: &UsageResponse) -> Result<()> {
+        let plan = response
+            .plan_type
+            .as_ref()
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut body = HashMap::new();
+        body.insert("stage_description", pace.stage_description().to_string());
+        body.insert("format_delta", pace.format_delta());
+        body.insert("format_eta", pace.format_eta());
+        body.insert("plan_type", plan);
+
+        self.client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .context("Failed to send webhook alert")?
+            .error_for_status()
+            .context("Webhook endpoint returned an error")?;
+        Ok(())
+    }
+}
+
+pub struct DesktopSink;
+
+impl AlertSink for DesktopSink {
+    fn send(&self, pace: &UsagePace, _response: &UsageResponse) -> Result<()> {
+        notify_rust::Notification::new()
+            .summary("Codex usage")
+            .body(&format!("{} ({}) · ETA {}", pace.stage_description(), pace.format_delta(), pace.format_eta()))
+            .show()
+            .context("Failed to show desktop notification")?;
+        Ok(())
+    }
+}
+
+/// Polls `fetch_usage` on `interval`, redisplaying each tick and firing every
+/// sink in `sinks` the moment the stage crosses into an alertable one
+/// (debounced so the same stage doesn't re-fire every tick).
+pub async fn watch(
+    mut credentials: Credentials,
+    interval: Duration,
+    sinks: &[Box<dyn AlertSink>],
+    discord_client_id: Option<&str>,
+    no_hyperlinks: bool,
+) -> Result<()> {
+    let fetcher = UsageFetcher::new();
+    let mut last_alerted = false;
+
+    #[cfg(feature = "discord")]
+    let mut discord = discord_client_id.and_then(|id| {
+        tokio::task::block_in_place(|| crate::discord::DiscordPresence::connect(id).ok())
+    });
+    #[cfg(not(feature = "discord"))]
+    let _ = discord_client_id;
+
+    loop {
+        let response = fetcher.fetch_usage(&mut credentials).await?;
+        crate::record_history(&response);
+        display::display_usage(&response, no_hyperlinks);
+
+        if let Some(pace) = pace_from_response(&response) {
+            let alertable = is_alertable(&pace);
+            if alertable && !last_alerted {
+                // Sinks (e.g. the webhook's blocking HTTP call) are
+                // synchronous; run them via `block_in_place` so a slow or
+                // unreachable endpoint can't stall this async loop.
+                tokio::task::block_in_place(|| {
+                    for sink in sinks {
+                        let _ = sink.send(&pace, &response);
+                    }
+                });
+            }
+            last_alerted = alertable;
+
+            #[cfg(feature = "discord")]
+            if let Some(presence) = discord.as_mut() {
+                tokio::task::block_in_place(|| {
+                    let _ = presence.update(&pace);
+                });
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval_units() {
+        assert_eq!(parse_interval("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_interval("15m").unwrap(), Duration::from_secs(15 * 60));
+        assert_eq!(parse_interval("2h").unwrap(), Duration::from_secs(2 * 60 * 60));
+    }
+
+    #[test]
+    fn test_parse_interval_named_cadences() {
+        assert_eq!(parse_interval("hourly").unwrap(), Duration::from_secs(60 * 60));
+        assert_eq!(parse_interval("twice-daily").unwrap(), Duration::from_secs(12 * 60 * 60));
+    }
+
+    #[test]
+    fn test_parse_interval_rejects_garbage() {
+        assert!(parse_interval("soon").is_err());
+        assert!(parse_interval("15x").is_err());
+    }
+}